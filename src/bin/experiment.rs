@@ -1,9 +1,16 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+};
+
 use approximate_rs::{
+    blocked::BlockedBloomFilter,
     bloom::BloomFilterWrapper,
-    cli::{Cli, QueryCommands},
+    cli::{Cli, QueryCommands, SweepArgs},
+    counting::CountingBloomFilter,
     fingerprint::FingerprintArray,
     mphf::MphfWrapper,
-    utils::{run_experiment, SplitKeys},
+    utils::{run_experiment, SplitKeys, SweepReport},
 };
 use clap::Parser;
 use eyre::{Context, Result};
@@ -18,25 +25,77 @@ fn main() -> Result<()> {
         args.total_keys as usize,
         args.kmer_size,
     );
+
+    if let QueryCommands::Sweep(x) = &args.command {
+        return run_sweep(&keys, x);
+    }
+
+    if matches!(
+        args.command,
+        QueryCommands::CountingBloomFilter(_) | QueryCommands::BlockedBloomFilter(_)
+    ) && (args.load_path.is_some() || args.save_path.is_some())
+    {
+        eyre::bail!("--load-path/--save-path are not supported for this subcommand (counting-bloom-filter and blocked-bloom-filter don't persist)");
+    }
+
     let results = match args.command {
         QueryCommands::Fingerprint(x) => {
-            let fa = FingerprintArray::new(&keys.positives, x.width as usize);
+            let fa = match &args.load_path {
+                Some(path) => {
+                    let mut reader =
+                        BufReader::new(File::open(path).wrap_err("Could not open filter file")?);
+                    FingerprintArray::load(&mut reader)?
+                }
+                None => FingerprintArray::new(&keys.positives, x.width as usize),
+            };
+            if let Some(path) = &args.save_path {
+                let mut writer =
+                    BufWriter::new(File::create(path).wrap_err("Could not create filter file")?);
+                fa.save(&mut writer)?;
+            }
             run_experiment(&keys, &fa)
-            // assert_no_false_negatives(&keys.positives, &fa);
-            // test_false_positive_rate(&keys.negatives, &fa)
         }
         QueryCommands::Mphf => {
-            let mphf = MphfWrapper::new(&keys.positives);
-            // assert_no_false_negatives(&keys.positives, &mphf);
+            let mphf = match &args.load_path {
+                Some(path) => {
+                    let mut reader =
+                        BufReader::new(File::open(path).wrap_err("Could not open filter file")?);
+                    MphfWrapper::load(&mut reader)?
+                }
+                None => MphfWrapper::new(&keys.positives),
+            };
+            if let Some(path) = &args.save_path {
+                let mut writer =
+                    BufWriter::new(File::create(path).wrap_err("Could not create filter file")?);
+                mphf.save(&mut writer)?;
+            }
             run_experiment(&keys, &mphf)
-            // test_false_positive_rate(&keys.negatives, &mphf)
         }
         QueryCommands::BloomFilter(x) => {
-            let bf = BloomFilterWrapper::new(&keys.positives, x.fpp);
+            let bf = match &args.load_path {
+                Some(path) => {
+                    let mut reader =
+                        BufReader::new(File::open(path).wrap_err("Could not open filter file")?);
+                    BloomFilterWrapper::load(&mut reader)?
+                }
+                None => BloomFilterWrapper::new(&keys.positives, x.fpp),
+            };
+            if let Some(path) = &args.save_path {
+                let mut writer =
+                    BufWriter::new(File::create(path).wrap_err("Could not create filter file")?);
+                bf.save(&mut writer)?;
+            }
             run_experiment(&keys, &bf)
-            // assert_no_false_negatives(&keys.positives, &bf);
-            // test_false_positive_rate(&keys.negatives, &bf)
         }
+        QueryCommands::CountingBloomFilter(x) => {
+            let cbf = CountingBloomFilter::new(&keys.positives, x.fpp);
+            run_experiment(&keys, &cbf)
+        }
+        QueryCommands::BlockedBloomFilter(x) => {
+            let bbf = BlockedBloomFilter::new(&keys.positives, x.fpp);
+            run_experiment(&keys, &bbf)
+        }
+        QueryCommands::Sweep(_) => unreachable!("handled above before keys were consumed"),
     };
     println!(
         "{}",
@@ -44,3 +103,49 @@ fn main() -> Result<()> {
     );
     Ok(())
 }
+
+/// Builds every structure across the parameter grid in `args` for a single
+/// generated `SplitKeys` set, emitting one [`SweepReport`] row per
+/// configuration.
+fn run_sweep(keys: &SplitKeys, args: &SweepArgs) -> Result<()> {
+    let mut report = SweepReport::default();
+
+    args.fingerprint_widths.iter().for_each(|&width| {
+        let fa = FingerprintArray::new(&keys.positives, width as usize);
+        let results = run_experiment(keys, &fa);
+        report.push_result("fingerprint", format!("width={width}"), None, &results);
+    });
+
+    args.bloom_fpps.iter().for_each(|&fpp| {
+        let bf = BloomFilterWrapper::new(&keys.positives, fpp);
+        let results = run_experiment(keys, &bf);
+        report.push_result("bloom", format!("fpp={fpp}"), Some(fpp), &results);
+
+        let bbf = BlockedBloomFilter::new(&keys.positives, fpp);
+        let results = run_experiment(keys, &bbf);
+        report.push_result("blocked_bloom", format!("fpp={fpp}"), Some(fpp), &results);
+
+        let cbf = CountingBloomFilter::new(&keys.positives, fpp);
+        let results = run_experiment(keys, &cbf);
+        report.push_result("counting_bloom", format!("fpp={fpp}"), Some(fpp), &results);
+    });
+
+    let mphf = MphfWrapper::new(&keys.positives);
+    let results = run_experiment(keys, &mphf);
+    report.push_result("mphf", String::new(), None, &results);
+
+    if let Some(path) = &args.csv_path {
+        std::fs::write(path, report.to_csv()).wrap_err("Could not write CSV sweep report")?;
+    }
+    if let Some(path) = &args.json_path {
+        let json =
+            serde_json::to_string_pretty(&report).wrap_err("Could not serialize sweep report")?;
+        std::fs::write(path, json).wrap_err("Could not write JSON sweep report")?;
+    }
+
+    println!(
+        "{}",
+        serde_json::to_string(&report).wrap_err("Could not serialize sweep report")?
+    );
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{BuildHasher, BuildHasherDefault, Hash},
+    marker::PhantomData,
+};
+
+use crate::utils::{optimal_bloom_params, KnowsSize, MembershipSupport};
+
+/// Bits per block: exactly one cache line.
+const BLOCK_BITS: usize = 512;
+const BLOCK_WORDS: usize = BLOCK_BITS / 64;
+
+/// A register-blocked Bloom filter tuned for memory locality.
+///
+/// The bit array is partitioned into fixed-size blocks of one cache line
+/// (512 bits) packed into a contiguous `Vec<u64>`. Each key is hashed once to
+/// pick a block, then the remaining hash bits choose `k` bit offsets
+/// *within that single block* via the Kirsch-Mitzenmacher double-hash
+/// recurrence, so every `contains` touches only one cache line instead of
+/// `k` scattered words. This trades a slightly higher false positive rate
+/// than [`BloomFilterWrapper`](crate::bloom::BloomFilterWrapper) for far
+/// better query throughput on large filters.
+#[derive(Debug)]
+pub struct BlockedBloomFilter<T, H = BuildHasherDefault<DefaultHasher>> {
+    blocks: Vec<u64>,
+    num_blocks: usize,
+    k: u32,
+    build_hasher: H,
+    _marker: PhantomData<T>,
+}
+
+impl<T> BlockedBloomFilter<T, BuildHasherDefault<DefaultHasher>>
+where
+    T: Hash,
+{
+    /// Creates a blocked bloom filter sized for `keys.len()` items at the
+    /// given false positive rate.
+    ///
+    /// Restricting hash bits to a single cache-line block raises the false
+    /// positive rate above a non-blocked [`BloomFilterWrapper`]'s for the
+    /// same `fpp` target, so this checks against a looser bound than
+    /// `bloom.rs`'s doctest.
+    ///
+    /// ```rust
+    /// # use approximate_rs::{blocked::*, utils::*};
+    /// let positive_keys: Vec<u32> = (1..1000).collect();
+    /// let negative_keys: Vec<u32> = (1000..10_000).collect();
+    /// let fpp = 0.01;
+    /// let tol = 0.02;
+    /// let filter: BlockedBloomFilter<u32> = BlockedBloomFilter::new(&positive_keys, fpp);
+    /// let true_positives = positive_keys.iter().filter(|key| filter.contains(key)).count();
+    /// let false_positives = negative_keys.iter().filter(|key| filter.contains(key)).count();
+    /// let expected_false_positives = ((fpp + tol) * negative_keys.len() as f64) as usize;
+    /// assert_eq!(true_positives, positive_keys.len());
+    /// assert!(false_positives < expected_false_positives);
+    /// ```
+    pub fn new(keys: &[T], false_positive_rate: f64) -> Self {
+        Self::with_hasher(keys, false_positive_rate, BuildHasherDefault::default())
+    }
+}
+
+impl<T, H> BlockedBloomFilter<T, H>
+where
+    T: Hash,
+    H: BuildHasher,
+{
+    /// Creates a blocked bloom filter using a caller-supplied `BuildHasher`
+    /// instead of the default `DefaultHasher`.
+    pub fn with_hasher(keys: &[T], false_positive_rate: f64, build_hasher: H) -> Self {
+        let (m, k) = optimal_bloom_params(keys.len(), false_positive_rate);
+        let num_blocks = m.div_ceil(BLOCK_BITS).max(1);
+        let mut filter = Self {
+            blocks: vec![0; num_blocks * BLOCK_WORDS],
+            num_blocks,
+            k,
+            build_hasher,
+            _marker: PhantomData,
+        };
+        keys.iter().for_each(|key| filter.insert(key));
+        filter
+    }
+
+    /// Bit indices touched by `item`, all within the single block selected
+    /// by `h1`.
+    fn indices(&self, item: &T) -> impl Iterator<Item = usize> + '_ {
+        let h1 = self.build_hasher.hash_one(item);
+        let num_blocks = self.num_blocks as u64;
+        let base = (h1 % num_blocks) as usize * BLOCK_BITS;
+        let rest = h1 / num_blocks;
+        let h2 = self.build_hasher.hash_one(rest);
+        (0..self.k).map(move |i| {
+            base + (rest.wrapping_add((i as u64).wrapping_mul(h2)) % BLOCK_BITS as u64) as usize
+        })
+    }
+
+    fn insert(&mut self, item: &T) {
+        let indices: Vec<usize> = self.indices(item).collect();
+        indices.into_iter().for_each(|idx| {
+            self.blocks[idx >> 6] |= 1 << (idx & 63);
+        });
+    }
+}
+
+impl<T, H> MembershipSupport<T> for BlockedBloomFilter<T, H>
+where
+    T: Hash,
+    H: BuildHasher,
+{
+    fn contains(&self, item: &T) -> bool {
+        self.indices(item)
+            .all(|idx| (self.blocks[idx >> 6] >> (idx & 63)) & 1 == 1)
+    }
+}
+
+impl<T, H> KnowsSize for BlockedBloomFilter<T, H> {
+    fn size_in_bytes(&self) -> usize {
+        self.blocks.len() * std::mem::size_of::<u64>()
+            + std::mem::size_of::<usize>()
+            + std::mem::size_of::<u32>()
+    }
+}
@@ -1,14 +1,31 @@
-use probabilistic_collections::bloom::BloomFilter;
-use std::hash::Hash;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{BuildHasher, BuildHasherDefault, Hash},
+    io::{Read, Write},
+    marker::PhantomData,
+};
 
-use crate::utils::{KnowsSize, MembershipSupport};
+use crate::{
+    persist::{read_header, read_u32, read_u64, write_header, write_u32, write_u64, PersistError, StructureKind},
+    utils::{double_hash_indices, optimal_bloom_params, KnowsSize, MembershipSupport},
+};
 
+/// A native Bloom filter. Bit positions are derived from two base hash
+/// values via Kirsch-Mitzenmacher double hashing (see
+/// [`double_hash_indices`](crate::utils::double_hash_indices)), so each key
+/// is hashed only once regardless of `k`. Generic over the hasher so a
+/// faster non-cryptographic hash can be plugged in in place of the default
+/// `DefaultHasher`.
 #[derive(Debug)]
-pub struct BloomFilterWrapper<T> {
-    pub bloom_filter: BloomFilter<T>,
+pub struct BloomFilterWrapper<T, H = BuildHasherDefault<DefaultHasher>> {
+    bits: Vec<u64>,
+    m: usize,
+    k: u32,
+    build_hasher: H,
+    _marker: PhantomData<T>,
 }
 
-impl<T> BloomFilterWrapper<T>
+impl<T> BloomFilterWrapper<T, BuildHasherDefault<DefaultHasher>>
 where
     T: Hash,
 {
@@ -28,28 +45,91 @@ where
 /// assert!(true_positives == positive_keys.len());
 /// assert!(false_positives < expected_fasle_positives);
 /// ```
-    pub fn new(keys: &Vec<T>, false_positive_rate: f64) -> Self {
-
-        let mut bloom_filter: BloomFilter<T> = BloomFilter::new(keys.len(), false_positive_rate);
-        keys.iter().for_each(|key| bloom_filter.insert(key));
-        Self { bloom_filter }
+    pub fn new(keys: &[T], false_positive_rate: f64) -> Self {
+        Self::with_hasher(keys, false_positive_rate, BuildHasherDefault::default())
     }
 }
 
-impl<T> MembershipSupport<T> for BloomFilterWrapper<T>
+impl<T, H> BloomFilterWrapper<T, H>
 where
     T: Hash,
+    H: BuildHasher,
 {
-    fn contains(&self, item: &T) -> bool {
-        self.bloom_filter.contains(item)
+    /// Creates a bloom filter using a caller-supplied `BuildHasher` instead
+    /// of the default `DefaultHasher`, e.g. a faster non-cryptographic hash.
+    pub fn with_hasher(keys: &[T], false_positive_rate: f64, build_hasher: H) -> Self {
+        let (m, k) = optimal_bloom_params(keys.len(), false_positive_rate);
+        let mut filter = Self {
+            bits: vec![0; m.div_ceil(64)],
+            m,
+            k,
+            build_hasher,
+            _marker: PhantomData,
+        };
+        keys.iter().for_each(|key| filter.insert(key));
+        filter
+    }
+
+    fn insert(&mut self, item: &T) {
+        let indices: Vec<usize> =
+            double_hash_indices(&self.build_hasher, item, self.m, self.k).collect();
+        indices.into_iter().for_each(|idx| {
+            self.bits[idx >> 6] |= 1 << (idx & 63);
+        });
+    }
+
+    /// Writes this filter to `w` as a self-describing, versioned blob: a
+    /// common header (see [`persist`](crate::persist)) followed by `m`, `k`,
+    /// and the packed bit words.
+    pub fn save<W: Write>(&self, w: &mut W) -> Result<(), PersistError> {
+        write_header(w, StructureKind::Bloom)?;
+        write_u64(w, self.m as u64)?;
+        write_u32(w, self.k)?;
+        write_u64(w, self.bits.len() as u64)?;
+        self.bits
+            .iter()
+            .try_for_each(|word| w.write_all(&word.to_le_bytes()).map_err(PersistError::from))
+    }
+
+    /// Reads back a filter previously written by [`save`](Self::save),
+    /// reconstructing the hasher via `H::default()`.
+    pub fn load<R: Read>(r: &mut R) -> Result<Self, PersistError>
+    where
+        H: Default,
+    {
+        read_header(r, StructureKind::Bloom)?;
+        let m = read_u64(r)? as usize;
+        let k = read_u32(r)?;
+        let words = read_u64(r)? as usize;
+        let mut bits = vec![0u64; words];
+        for word in bits.iter_mut() {
+            *word = read_u64(r)?;
+        }
+        Ok(Self {
+            bits,
+            m,
+            k,
+            build_hasher: H::default(),
+            _marker: PhantomData,
+        })
     }
 }
 
-impl<T> KnowsSize for BloomFilterWrapper<T>
+impl<T, H> MembershipSupport<T> for BloomFilterWrapper<T, H>
 where
-    T: Hash + serde::ser::Serialize,
+    T: Hash,
+    H: BuildHasher,
 {
+    fn contains(&self, item: &T) -> bool {
+        double_hash_indices(&self.build_hasher, item, self.m, self.k)
+            .all(|idx| (self.bits[idx >> 6] >> (idx & 63)) & 1 == 1)
+    }
+}
+
+impl<T, H> KnowsSize for BloomFilterWrapper<T, H> {
     fn size_in_bytes(&self) -> usize {
-        bincode::serialized_size(&self.bloom_filter).unwrap() as usize
+        self.bits.len() * std::mem::size_of::<u64>()
+            + std::mem::size_of::<usize>()
+            + std::mem::size_of::<u32>()
     }
 }
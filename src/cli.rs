@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -21,6 +23,39 @@ pub struct FingerprintArgs {
     pub width: u8,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct CountingBloomFilterArgs {
+    #[arg(long)]
+    /// The desired false positive rate
+    pub fpp: f64,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct BlockedBloomFilterArgs {
+    #[arg(long)]
+    /// The desired false positive rate
+    pub fpp: f64,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SweepArgs {
+    /// Fingerprint widths, in bits, to sweep
+    #[arg(long, value_delimiter = ',', default_value = "4,6,8,10,12,14,16")]
+    pub fingerprint_widths: Vec<u8>,
+
+    /// False positive rates to sweep for every bloom-filter-family structure
+    #[arg(long, value_delimiter = ',', default_value = "0.1,0.01,0.001")]
+    pub bloom_fpps: Vec<f64>,
+
+    /// Write the sweep report as CSV to this path
+    #[arg(long)]
+    pub csv_path: Option<PathBuf>,
+
+    /// Write the sweep report as JSON to this path
+    #[arg(long)]
+    pub json_path: Option<PathBuf>,
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum QueryCommands {
     /// Use a bloom filter with a configurable false positive rate
@@ -29,6 +64,12 @@ pub enum QueryCommands {
     Mphf,
     /// Use a fingerprint array with a configurable fingerprint size to tune the false positive rate
     Fingerprint(FingerprintArgs),
+    /// Use a counting bloom filter, which supports removing keys as well as inserting them
+    CountingBloomFilter(CountingBloomFilterArgs),
+    /// Use a cache-aware blocked bloom filter for better query throughput on large filters
+    BlockedBloomFilter(BlockedBloomFilterArgs),
+    /// Build every structure across a parameter grid and report the space/accuracy/speed tradeoffs
+    Sweep(SweepArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -48,4 +89,14 @@ pub struct Cli {
     /// The number of characters in each key
     #[arg(short, long, default_value = "30")]
     pub kmer_size: u8,
+
+    /// Build the filter, write it to this path, then query it as usual.
+    /// Lets a filter built once be reused instead of rebuilt every run.
+    #[arg(long)]
+    pub save_path: Option<PathBuf>,
+
+    /// Load a previously built filter from this path instead of rebuilding
+    /// it from freshly generated keys.
+    #[arg(long)]
+    pub load_path: Option<PathBuf>,
 }
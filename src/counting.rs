@@ -0,0 +1,231 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{BuildHasher, BuildHasherDefault, Hash},
+    marker::PhantomData,
+};
+
+use crate::utils::{double_hash_indices, optimal_bloom_params, KnowsSize, MembershipSupport};
+
+/// A counter array backing a [`CountingBloomFilter`].
+///
+/// Counters saturate rather than wrap on overflow: once a counter reaches its
+/// maximum value it can no longer be safely decremented: an earlier
+/// `increment` was silently dropped, so decrements can bring it to zero while
+/// keys that map to it are still present, making them read as absent.
+pub trait CounterStorage {
+    fn new(len: usize) -> Self;
+    fn increment(&mut self, index: usize);
+    fn decrement(&mut self, index: usize);
+    fn is_nonzero(&self, index: usize) -> bool;
+    fn size_in_bytes(&self) -> usize;
+}
+
+/// One byte per counter, saturating at `u8::MAX`.
+#[derive(Debug)]
+pub struct U8Counters(Vec<u8>);
+
+impl CounterStorage for U8Counters {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len])
+    }
+
+    fn increment(&mut self, index: usize) {
+        self.0[index] = self.0[index].saturating_add(1);
+    }
+
+    fn decrement(&mut self, index: usize) {
+        self.0[index] = self.0[index].saturating_sub(1);
+    }
+
+    fn is_nonzero(&self, index: usize) -> bool {
+        self.0[index] != 0
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Two 4-bit counters packed per byte, saturating at 15. Halves the memory of
+/// [`U8Counters`] at the cost of a lower ceiling before saturation.
+#[derive(Debug)]
+pub struct U4Counters(Vec<u8>);
+
+const U4_MAX: u8 = 0x0F;
+
+impl U4Counters {
+    fn get(&self, index: usize) -> u8 {
+        let byte = self.0[index / 2];
+        if index.is_multiple_of(2) {
+            byte & U4_MAX
+        } else {
+            byte >> 4
+        }
+    }
+
+    fn set(&mut self, index: usize, value: u8) {
+        let byte = &mut self.0[index / 2];
+        if index.is_multiple_of(2) {
+            *byte = (*byte & 0xF0) | (value & U4_MAX);
+        } else {
+            *byte = (*byte & 0x0F) | (value << 4);
+        }
+    }
+}
+
+impl CounterStorage for U4Counters {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(2)])
+    }
+
+    fn increment(&mut self, index: usize) {
+        let value = self.get(index);
+        if value < U4_MAX {
+            self.set(index, value + 1);
+        }
+    }
+
+    fn decrement(&mut self, index: usize) {
+        let value = self.get(index);
+        if value > 0 {
+            self.set(index, value - 1);
+        }
+    }
+
+    fn is_nonzero(&self, index: usize) -> bool {
+        self.get(index) != 0
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Membership queries that can be revoked after the fact, unlike
+/// [`MembershipSupport`] alone which only ever grows a static key set (see
+/// `SplitKeys::new`).
+pub trait DynamicMembership<T> {
+    fn insert(&mut self, item: &T);
+    fn remove(&mut self, item: &T);
+}
+
+/// A Bloom filter backed by small saturating counters instead of single bits,
+/// so keys can be removed as well as inserted.
+///
+/// Unlike [`BloomFilterWrapper`](crate::bloom::BloomFilterWrapper), which is
+/// insert-only, this is suited to mutable sets such as sliding windows or
+/// cache eviction where membership needs to shrink over time.
+#[derive(Debug)]
+pub struct CountingBloomFilter<T, C = U4Counters, H = BuildHasherDefault<DefaultHasher>> {
+    counters: C,
+    m: usize,
+    k: u32,
+    build_hasher: H,
+    _marker: PhantomData<T>,
+}
+
+impl<T> CountingBloomFilter<T, U4Counters, BuildHasherDefault<DefaultHasher>>
+where
+    T: Hash,
+{
+    /// Creates a counting bloom filter sized for `keys.len()` items at the
+    /// given false positive rate.
+    ///
+    /// ```rust
+    /// use approximate_rs::counting::{CountingBloomFilter, DynamicMembership};
+    /// use approximate_rs::utils::MembershipSupport;
+    ///
+    /// let keys: Vec<u32> = (1..1000).collect();
+    /// let mut filter = CountingBloomFilter::new(&keys, 0.01);
+    /// assert!(keys.iter().all(|key| filter.contains(key)));
+    ///
+    /// filter.remove(&keys[0]);
+    /// assert!(!filter.contains(&keys[0]));
+    /// assert!(filter.contains(&keys[1]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not in `(0, 1)` (see
+    /// [`optimal_bloom_params`](crate::utils::optimal_bloom_params)) instead
+    /// of attempting an unbounded allocation:
+    ///
+    /// ```rust,should_panic
+    /// use approximate_rs::counting::CountingBloomFilter;
+    ///
+    /// let keys: Vec<u32> = (1..1000).collect();
+    /// let _ = CountingBloomFilter::new(&keys, 0.0);
+    /// ```
+    pub fn new(keys: &[T], false_positive_rate: f64) -> Self {
+        Self::with_hasher(keys, false_positive_rate, BuildHasherDefault::default())
+    }
+}
+
+impl<T, C, H> CountingBloomFilter<T, C, H>
+where
+    T: Hash,
+    C: CounterStorage,
+    H: BuildHasher,
+{
+    /// Creates a counting bloom filter using a caller-supplied `BuildHasher`
+    /// instead of the default `DefaultHasher`.
+    pub fn with_hasher(keys: &[T], false_positive_rate: f64, build_hasher: H) -> Self {
+        let (m, k) = optimal_bloom_params(keys.len(), false_positive_rate);
+        let mut filter = Self {
+            counters: C::new(m),
+            m,
+            k,
+            build_hasher,
+            _marker: PhantomData,
+        };
+        keys.iter().for_each(|key| filter.insert(key));
+        filter
+    }
+
+    fn indices(&self, item: &T) -> impl Iterator<Item = usize> + '_ {
+        double_hash_indices(&self.build_hasher, item, self.m, self.k)
+    }
+}
+
+impl<T, C, H> DynamicMembership<T> for CountingBloomFilter<T, C, H>
+where
+    T: Hash,
+    C: CounterStorage,
+    H: BuildHasher,
+{
+    fn insert(&mut self, item: &T) {
+        self.indices(item)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .for_each(|idx| self.counters.increment(idx));
+    }
+
+    fn remove(&mut self, item: &T) {
+        self.indices(item)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .for_each(|idx| self.counters.decrement(idx));
+    }
+}
+
+impl<T, C, H> MembershipSupport<T> for CountingBloomFilter<T, C, H>
+where
+    T: Hash,
+    C: CounterStorage,
+    H: BuildHasher,
+{
+    fn contains(&self, item: &T) -> bool {
+        self.indices(item).all(|idx| self.counters.is_nonzero(idx))
+    }
+}
+
+impl<T, C, H> KnowsSize for CountingBloomFilter<T, C, H>
+where
+    C: CounterStorage,
+{
+    fn size_in_bytes(&self) -> usize {
+        self.counters.size_in_bytes()
+            + std::mem::size_of::<usize>()
+            + std::mem::size_of::<u32>()
+    }
+}
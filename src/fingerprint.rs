@@ -1,32 +1,31 @@
 use std::{
     collections::hash_map::DefaultHasher,
     fmt::Debug,
-    hash::{Hash, Hasher},
+    hash::{BuildHasher, BuildHasherDefault, Hash},
+    io::{Read, Write},
 };
 
 use boomphf::Mphf;
 use sucds::{int_vectors::CompactVector, Serializable};
 
-use crate::utils::{KnowsSize, MembershipSupport};
+use crate::{
+    persist::{read_header, read_u64, write_header, write_u64, PersistError, StructureKind},
+    utils::{KnowsSize, MembershipSupport},
+};
 const GAMMA: f64 = 1.7;
-pub struct FingerprintArray<T> {
+pub struct FingerprintArray<T, H = BuildHasherDefault<DefaultHasher>> {
     mphf: Mphf<T>,
     int_vector: CompactVector,
     mask: u64,
+    build_hasher: H,
 }
 
-impl<T> FingerprintArray<T>
+impl<T> FingerprintArray<T, BuildHasherDefault<DefaultHasher>>
 where
     T: Hash + Debug,
 {
-    pub fn fingerprint(&self, item: &T) -> u64 {
-        let mut hasher = DefaultHasher::default();
-        item.hash(&mut hasher);
-        hasher.finish() & self.mask
-    }
-
     /// Create a fingerprint array with the specified width containing the given keys
-    /// 
+    ///
     /// ```rust
     /// use approximate_rs::{utils::*, fingerprint::*};
     /// let positive_keys: Vec<u32> = (1..=1000_u32).into_iter().collect();
@@ -50,6 +49,23 @@ where
     /// assert_eq!(true_positives, positive_keys.len());
     /// ```
     pub fn new(objects: &[T], fingerprint_size: usize) -> Self {
+        Self::with_hasher(objects, fingerprint_size, BuildHasherDefault::default())
+    }
+}
+
+impl<T, H> FingerprintArray<T, H>
+where
+    T: Hash + Debug,
+    H: BuildHasher,
+{
+    pub fn fingerprint(&self, item: &T) -> u64 {
+        self.build_hasher.hash_one(item) & self.mask
+    }
+
+    /// Create a fingerprint array using a caller-supplied `BuildHasher`
+    /// instead of the default `DefaultHasher`, e.g. a faster
+    /// non-cryptographic hash.
+    pub fn with_hasher(objects: &[T], fingerprint_size: usize, build_hasher: H) -> Self {
         let mphf = Mphf::new(GAMMA, objects);
         let mask = (1_u64 << fingerprint_size) - 1;
         let mut int_vector = CompactVector::with_capacity(objects.len(), fingerprint_size)
@@ -57,9 +73,7 @@ where
         (0..objects.len())
             .for_each(|_| int_vector.push_int(0).expect("The insert should be valid"));
         objects.iter().for_each(|item| {
-            let mut hasher = DefaultHasher::new();
-            item.hash(&mut hasher);
-            let fingerprint = hasher.finish() & mask;
+            let fingerprint = build_hasher.hash_one(item) & mask;
             int_vector
                 .set_int(mphf.hash(item) as usize, fingerprint as usize)
                 .expect("The hash value should be in bounds");
@@ -68,13 +82,57 @@ where
             mphf,
             mask,
             int_vector,
+            build_hasher,
         }
     }
+
+    /// Writes this fingerprint array to `w` as a self-describing, versioned
+    /// blob: a common header (see [`persist`](crate::persist)), the
+    /// fingerprint mask, the bincode-serialized MPHF, and the serialized
+    /// `CompactVector`.
+    ///
+    /// ```rust
+    /// use approximate_rs::{fingerprint::FingerprintArray, utils::MembershipSupport};
+    ///
+    /// let keys: Vec<u32> = (1..1000).collect();
+    /// let fa: FingerprintArray<u32> = FingerprintArray::new(&keys, 7);
+    ///
+    /// let mut bytes = Vec::new();
+    /// fa.save(&mut bytes).unwrap();
+    /// let loaded: FingerprintArray<u32> = FingerprintArray::load(&mut bytes.as_slice()).unwrap();
+    /// assert!(keys.iter().all(|key| loaded.contains(key)));
+    /// ```
+    pub fn save<W: Write>(&self, w: &mut W) -> Result<(), PersistError> {
+        write_header(w, StructureKind::Fingerprint)?;
+        write_u64(w, self.mask)?;
+        bincode::serialize_into(&mut *w, &self.mphf)?;
+        self.int_vector.serialize_into(w)?;
+        Ok(())
+    }
+
+    /// Reads back a fingerprint array previously written by
+    /// [`save`](Self::save), reconstructing the hasher via `H::default()`.
+    pub fn load<R: Read>(r: &mut R) -> Result<Self, PersistError>
+    where
+        H: Default,
+    {
+        read_header(r, StructureKind::Fingerprint)?;
+        let mask = read_u64(r)?;
+        let mphf = bincode::deserialize_from(&mut *r)?;
+        let int_vector = CompactVector::deserialize_from(r)?;
+        Ok(Self {
+            mphf,
+            mask,
+            int_vector,
+            build_hasher: H::default(),
+        })
+    }
 }
 
-impl<T> MembershipSupport<T> for FingerprintArray<T>
+impl<T, H> MembershipSupport<T> for FingerprintArray<T, H>
 where
     T: Hash + Debug,
+    H: BuildHasher,
 {
     fn contains(&self, item: &T) -> bool {
         match self.mphf.try_hash(item) {
@@ -90,7 +148,7 @@ where
     }
 }
 
-impl<T> KnowsSize for FingerprintArray<T>
+impl<T, H> KnowsSize for FingerprintArray<T, H>
 where
     T: Hash + Debug,
 {
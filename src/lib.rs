@@ -0,0 +1,8 @@
+pub mod blocked;
+pub mod bloom;
+pub mod cli;
+pub mod counting;
+pub mod fingerprint;
+pub mod mphf;
+pub mod persist;
+pub mod utils;
@@ -1,8 +1,15 @@
-use std::{fmt::Debug, hash::Hash};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{Read, Write},
+};
 
 use boomphf::Mphf;
 
-use crate::utils::{KnowsSize, MembershipSupport};
+use crate::{
+    persist::{read_header, write_header, PersistError, StructureKind},
+    utils::{KnowsSize, MembershipSupport},
+};
 const GAMMA: f64 = 1.7;
 pub struct MphfWrapper<T> {
     mphf: Mphf<T>,
@@ -16,6 +23,34 @@ where
         let mphf = Mphf::new(GAMMA, objects);
         Self { mphf }
     }
+
+    /// Writes this MPHF to `w` as a self-describing, versioned blob: a
+    /// common header (see [`persist`](crate::persist)) followed by the
+    /// bincode-serialized MPHF.
+    ///
+    /// ```rust
+    /// use approximate_rs::{mphf::MphfWrapper, utils::MembershipSupport};
+    ///
+    /// let keys: Vec<u32> = (1..1000).collect();
+    /// let mphf = MphfWrapper::new(&keys);
+    ///
+    /// let mut bytes = Vec::new();
+    /// mphf.save(&mut bytes).unwrap();
+    /// let loaded = MphfWrapper::load(&mut bytes.as_slice()).unwrap();
+    /// assert!(keys.iter().all(|key| loaded.contains(key)));
+    /// ```
+    pub fn save<W: Write>(&self, w: &mut W) -> Result<(), PersistError> {
+        write_header(w, StructureKind::Mphf)?;
+        bincode::serialize_into(w, &self.mphf)?;
+        Ok(())
+    }
+
+    /// Reads back an MPHF previously written by [`save`](Self::save).
+    pub fn load<R: Read>(r: &mut R) -> Result<Self, PersistError> {
+        read_header(r, StructureKind::Mphf)?;
+        let mphf = bincode::deserialize_from(r)?;
+        Ok(Self { mphf })
+    }
 }
 
 impl<T> MembershipSupport<T> for MphfWrapper<T>
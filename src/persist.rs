@@ -0,0 +1,163 @@
+//! A common binary framing shared by every persisted filter: a magic
+//! number, format version, and [`StructureKind`] tag, followed by
+//! structure-specific data written by the caller (see
+//! [`BloomFilterWrapper::save`](crate::bloom::BloomFilterWrapper::save)).
+//!
+//! ```rust
+//! # use approximate_rs::{bloom::*, utils::*};
+//! let keys: Vec<u32> = (1..1000).collect();
+//! let filter: BloomFilterWrapper<u32> = BloomFilterWrapper::new(&keys, 0.01);
+//!
+//! let mut bytes = Vec::new();
+//! filter.save(&mut bytes).unwrap();
+//! let loaded: BloomFilterWrapper<u32> = BloomFilterWrapper::load(&mut bytes.as_slice()).unwrap();
+//! assert!(keys.iter().all(|key| loaded.contains(key)));
+//! ```
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 4] = b"APRX";
+const FORMAT_VERSION: u8 = 1;
+
+/// Which structure a persisted file holds, recorded in its header so a file
+/// built for one AMQ implementation can't silently be loaded as another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureKind {
+    Bloom,
+    Mphf,
+    Fingerprint,
+}
+
+impl StructureKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Bloom => 0,
+            Self::Mphf => 1,
+            Self::Fingerprint => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Bloom),
+            1 => Some(Self::Mphf),
+            2 => Some(Self::Fingerprint),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that can occur when saving or loading a persisted filter.
+#[derive(Debug)]
+pub enum PersistError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u8),
+    UnknownStructureKind(u8),
+    WrongStructureKind {
+        expected: StructureKind,
+        found: StructureKind,
+    },
+    Bincode(bincode::Error),
+    Sucds(anyhow::Error),
+}
+
+impl std::fmt::Display for PersistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::BadMagic => write!(f, "not an approximate-rs filter file (bad magic bytes)"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported filter format version {v}"),
+            Self::UnknownStructureKind(k) => write!(f, "unknown structure kind byte {k}"),
+            Self::WrongStructureKind { expected, found } => write!(
+                f,
+                "expected a {expected:?} filter but the file contains a {found:?} filter"
+            ),
+            Self::Bincode(e) => write!(f, "failed to (de)serialize filter contents: {e}"),
+            Self::Sucds(e) => write!(f, "failed to (de)serialize compact vector: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Bincode(e) => Some(e),
+            Self::Sucds(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PersistError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<bincode::Error> for PersistError {
+    fn from(e: bincode::Error) -> Self {
+        Self::Bincode(e)
+    }
+}
+
+impl From<anyhow::Error> for PersistError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Sucds(e)
+    }
+}
+
+/// Writes the magic bytes, format version, and structure kind shared by
+/// every persisted filter. Structure-specific parameters follow immediately
+/// after, written by the caller.
+pub(crate) fn write_header<W: Write>(w: &mut W, kind: StructureKind) -> Result<(), PersistError> {
+    w.write_all(MAGIC)?;
+    w.write_all(&[FORMAT_VERSION])?;
+    w.write_all(&[kind.to_byte()])?;
+    Ok(())
+}
+
+/// Reads and validates the common header, returning a typed error on any
+/// mismatch (bad magic, unsupported version, or wrong structure kind).
+pub(crate) fn read_header<R: Read>(r: &mut R, expected: StructureKind) -> Result<(), PersistError> {
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(PersistError::BadMagic);
+    }
+    let mut version = [0u8; 1];
+    r.read_exact(&mut version)?;
+    if version[0] != FORMAT_VERSION {
+        return Err(PersistError::UnsupportedVersion(version[0]));
+    }
+    let mut kind_byte = [0u8; 1];
+    r.read_exact(&mut kind_byte)?;
+    let found =
+        StructureKind::from_byte(kind_byte[0]).ok_or(PersistError::UnknownStructureKind(kind_byte[0]))?;
+    if found != expected {
+        return Err(PersistError::WrongStructureKind { expected, found });
+    }
+    Ok(())
+}
+
+pub(crate) fn write_u32<W: Write>(w: &mut W, value: u32) -> Result<(), PersistError> {
+    w.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+pub(crate) fn write_u64<W: Write>(w: &mut W, value: u64) -> Result<(), PersistError> {
+    w.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+pub(crate) fn read_u32<R: Read>(r: &mut R) -> Result<u32, PersistError> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub(crate) fn read_u64<R: Read>(r: &mut R) -> Result<u64, PersistError> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::HashSet,
     fmt::Debug,
+    hash::{BuildHasher, Hash},
     time::{Duration, Instant},
 };
 
@@ -60,6 +61,54 @@ impl Distribution<String> for Kmers {
     }
 }
 
+/// Computes the optimal bit-array size `m` and hash-function count `k` for a
+/// Bloom-filter-family structure holding `n` items at the given false
+/// positive rate.
+///
+/// # Panics
+///
+/// Panics if `false_positive_rate` is not in the open range `(0, 1)`. A rate
+/// of `0` drives `m` to infinity, which would otherwise saturate
+/// to `usize::MAX` and abort the process on the resulting allocation instead
+/// of failing cleanly.
+pub fn optimal_bloom_params(n: usize, false_positive_rate: f64) -> (usize, u32) {
+    assert!(
+        false_positive_rate > 0.0 && false_positive_rate < 1.0,
+        "false_positive_rate must be in (0, 1), got {false_positive_rate}"
+    );
+    let n = n.max(1);
+    let m = (-(n as f64) * false_positive_rate.ln() / std::f64::consts::LN_2.powi(2))
+        .ceil()
+        .max(1.0) as usize;
+    let k = (((m as f64) / (n as f64)) * std::f64::consts::LN_2)
+        .round()
+        .max(1.0) as u32;
+    (m, k)
+}
+
+/// Derives `k` slot indices in `0..m` for `item` from just two base hash
+/// values, using the Kirsch-Mitzenmacher double-hashing scheme
+/// `g_i(x) = (h1 + i * h2) % m`. This gives the same asymptotic false
+/// positive rate as `k` independent hash functions while hashing `item` only
+/// once, which matters for the throughput numbers recorded in
+/// [`ExperimentResults`].
+pub fn double_hash_indices<T, H>(
+    build_hasher: &H,
+    item: &T,
+    m: usize,
+    k: u32,
+) -> impl Iterator<Item = usize>
+where
+    T: Hash,
+    H: BuildHasher,
+{
+    let h1 = build_hasher.hash_one(item);
+    let h2 = build_hasher.hash_one(h1);
+
+    let m = m as u64;
+    (0..k).map(move |i| (h1.wrapping_add((i as u64).wrapping_mul(h2)) % m) as usize)
+}
+
 pub trait MembershipSupport<T> {
     fn contains(&self, item: &T) -> bool;
 }
@@ -132,6 +181,75 @@ impl SplitIndicies {
     }
 }
 
+/// One row of a [`SweepReport`]: the measured space/accuracy/speed tradeoff
+/// for a single AMQ structure at a single tuning parameter.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SweepRow {
+    pub structure: String,
+    pub parameter: String,
+    pub configured_fpp: Option<f64>,
+    pub measured_fpp: f64,
+    pub bytes: usize,
+    pub avg_positive_query_ns: f64,
+    pub avg_negative_query_ns: f64,
+}
+
+/// A reproducible space/accuracy/speed tradeoff study across AMQ
+/// implementations, built by running [`run_experiment`] once per
+/// configuration in a parameter grid.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SweepReport {
+    pub rows: Vec<SweepRow>,
+}
+
+impl SweepReport {
+    pub fn push_result(
+        &mut self,
+        structure: &str,
+        parameter: impl Into<String>,
+        configured_fpp: Option<f64>,
+        results: &ExperimentResults,
+    ) {
+        let measured_fpp =
+            results.false_positive_count as f64 / results.negative_keys.max(1) as f64;
+        let avg_positive_query_ns =
+            results.positives_query_duration.as_nanos() as f64 / results.positive_keys.max(1) as f64;
+        let avg_negative_query_ns =
+            results.negatives_query_duration.as_nanos() as f64 / results.negative_keys.max(1) as f64;
+        self.rows.push(SweepRow {
+            structure: structure.to_string(),
+            parameter: parameter.into(),
+            configured_fpp,
+            measured_fpp,
+            bytes: results.serialized_size,
+            avg_positive_query_ns,
+            avg_negative_query_ns,
+        });
+    }
+
+    /// Renders the report as CSV, one row per configuration.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "structure,parameter,configured_fpp,measured_fpp,bytes,avg_positive_query_ns,avg_negative_query_ns\n",
+        );
+        self.rows.iter().for_each(|row| {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                row.structure,
+                row.parameter,
+                row.configured_fpp
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                row.measured_fpp,
+                row.bytes,
+                row.avg_positive_query_ns,
+                row.avg_negative_query_ns,
+            ));
+        });
+        out
+    }
+}
+
 pub fn run_experiment<M>(keys: &SplitKeys, amq: &M) -> ExperimentResults
 where
     M: MembershipSupport<String> + KnowsSize,